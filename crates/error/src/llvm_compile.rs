@@ -1,12 +1,204 @@
 //! Error types and utilities to do with the compilation from LLVM IR to Cairo
 //! IR.
 
+use std::fmt::{Display, Formatter};
+
 use thiserror::Error;
 
+/// The result type for use during the process of compilation from LLVM IR to
+/// the Cairo IR.
+pub type Result<T> = std::result::Result<T, Error>;
+
 /// This error type is for use during the process of compilation from LLVM IR to
 /// the Cairo IR.
 #[derive(Clone, Debug, Error)]
 pub enum Error {
     #[error("Miscellaneous compilation error: {_0}")]
     Miscellaneous(String),
+
+    #[error("{context}: {error}")]
+    WithContext { context: ErrorContext, error: Box<Error> },
+}
+
+impl Error {
+    /// Attaches the provided `context` to this error.
+    ///
+    /// If the error already carries context, the existing context is kept and
+    /// only the fields that it is missing are filled in from `context`. This
+    /// means that context can be added as an error propagates outward—first
+    /// the instruction, then the block, then the function—without nesting
+    /// repeated wrappers.
+    #[must_use]
+    pub fn with_context(self, context: ErrorContext) -> Self {
+        match self {
+            Self::WithContext {
+                context: existing,
+                error,
+            } => Self::WithContext {
+                context: existing.merge(context),
+                error,
+            },
+            error => Self::WithContext {
+                context,
+                error: Box::new(error),
+            },
+        }
+    }
+
+    /// Gets the context attached to this error, if any.
+    #[must_use]
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::WithContext { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Gets the underlying error, stripped of any attached context.
+    #[must_use]
+    pub fn without_context(&self) -> &Self {
+        match self {
+            Self::WithContext { error, .. } => error.without_context(),
+            error => error,
+        }
+    }
+}
+
+/// The location in the LLVM IR at which a compilation error occurred.
+///
+/// All of the fields are optional, as the context is built up incrementally
+/// while an error propagates out of the compiler.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ErrorContext {
+    /// The name of the function being compiled.
+    pub function: Option<String>,
+
+    /// The name of the basic block being compiled.
+    pub block: Option<String>,
+
+    /// A textual summary of the instruction being compiled.
+    pub instruction: Option<String>,
+}
+
+impl ErrorContext {
+    /// Creates a new, empty, error context.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name of the function in which the error occurred.
+    #[must_use]
+    pub fn function(mut self, name: impl Into<String>) -> Self {
+        self.function = Some(name.into());
+        self
+    }
+
+    /// Sets the name of the basic block in which the error occurred.
+    #[must_use]
+    pub fn block(mut self, name: impl Into<String>) -> Self {
+        self.block = Some(name.into());
+        self
+    }
+
+    /// Sets the summary of the instruction at which the error occurred.
+    #[must_use]
+    pub fn instruction(mut self, summary: impl Into<String>) -> Self {
+        self.instruction = Some(summary.into());
+        self
+    }
+
+    /// Merges `self` with the `outer` context, preferring the fields of `self`
+    /// where both are present.
+    #[must_use]
+    pub fn merge(self, outer: Self) -> Self {
+        Self {
+            function:    self.function.or(outer.function),
+            block:       self.block.or(outer.block),
+            instruction: self.instruction.or(outer.instruction),
+        }
+    }
+}
+
+impl Display for ErrorContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(function) = &self.function {
+            parts.push(format!("function {function}"));
+        }
+        if let Some(block) = &self.block {
+            parts.push(format!("block {block}"));
+        }
+        if let Some(instruction) = &self.instruction {
+            parts.push(format!("instruction `{instruction}`"));
+        }
+
+        if parts.is_empty() {
+            write!(f, "in unknown location")
+        } else {
+            write!(f, "in {}", parts.join(", "))
+        }
+    }
+}
+
+/// An extension trait that allows attaching context to the error in a
+/// [`Result`] as it propagates.
+pub trait ResultExt<T> {
+    /// Attaches the context produced by `context` to the error, if any.
+    ///
+    /// The closure is only evaluated in the case of an error, so it is
+    /// acceptable for it to perform comparatively expensive work such as
+    /// printing the instruction.
+    ///
+    /// # Errors
+    ///
+    /// Returns the original error, with the context attached, if `self` is an
+    /// error.
+    fn with_context(self, context: impl FnOnce() -> ErrorContext) -> Result<T>;
+}
+
+impl<T> ResultExt<T> for Result<T> {
+    fn with_context(self, context: impl FnOnce() -> ErrorContext) -> Result<T> {
+        self.map_err(|e| e.with_context(context()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Error, ErrorContext, Result, ResultExt};
+
+    fn failing_instruction() -> Result<()> {
+        Err(Error::Miscellaneous("unsupported operation".to_string()))
+            .with_context(|| ErrorContext::new().instruction("%5 = udiv i64 %3, %4"))
+    }
+
+    #[test]
+    fn accumulates_context_while_propagating() {
+        let result = failing_instruction()
+            .with_context(|| ErrorContext::new().block("bb3"))
+            .with_context(|| ErrorContext::new().function("my_function"));
+        let error = result.expect_err("Result was not an error");
+
+        assert_eq!(
+            error.to_string(),
+            "in function my_function, block bb3, instruction `%5 = udiv i64 %3, %4`: \
+             Miscellaneous compilation error: unsupported operation"
+        );
+        assert!(matches!(
+            error.without_context(),
+            Error::Miscellaneous(msg) if msg == "unsupported operation"
+        ));
+    }
+
+    #[test]
+    fn prefers_innermost_context() {
+        let error = Error::Miscellaneous("oops".to_string())
+            .with_context(ErrorContext::new().block("bb1"))
+            .with_context(ErrorContext::new().function("f").block("bb2"));
+
+        assert_eq!(
+            error.context(),
+            Some(&ErrorContext::new().function("f").block("bb1"))
+        );
+    }
 }